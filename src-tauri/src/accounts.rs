@@ -0,0 +1,87 @@
+use keyring::Entry;
+
+use crate::{DEFAULT_PROFILE, SERVICE_NAME};
+
+/// Reserved keyring account that stores the JSON list of known profile
+/// names, since `keyring` has no API to enumerate entries for a service.
+const INDEX_ACCOUNT: &str = "__profile_index__";
+
+fn profile_entry(profile: &str) -> Result<Entry, String> {
+    Entry::new(SERVICE_NAME, profile).map_err(|e| e.to_string())
+}
+
+fn index_entry() -> Result<Entry, String> {
+    Entry::new(SERVICE_NAME, INDEX_ACCOUNT).map_err(|e| e.to_string())
+}
+
+/// Loads the profile index, backfilling `DEFAULT_PROFILE` into it if a key
+/// was already stored there from before named profiles existed — otherwise
+/// that credential would be usable but invisible to `list_api_keys`.
+fn load_profile_index() -> Result<Vec<String>, String> {
+    let entry = index_entry()?;
+    let mut profiles = match entry.get_password() {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| e.to_string())?,
+        Err(keyring::Error::NoEntry) => Vec::new(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if !profiles.iter().any(|p| p == DEFAULT_PROFILE) && get_stored_key(DEFAULT_PROFILE)?.is_some() {
+        profiles.push(DEFAULT_PROFILE.to_string());
+        save_profile_index(&profiles)?;
+    }
+
+    Ok(profiles)
+}
+
+fn save_profile_index(profiles: &[String]) -> Result<(), String> {
+    let entry = index_entry()?;
+    let json = serde_json::to_string(profiles).map_err(|e| e.to_string())?;
+    entry.set_password(&json).map_err(|e| e.to_string())
+}
+
+/// Looks up a stored key without going through the `#[tauri::command]`
+/// wrapper, so `rally_request` can resolve a `profile` field directly.
+pub(crate) fn get_stored_key(profile: &str) -> Result<Option<String>, String> {
+    let entry = profile_entry(profile)?;
+    match entry.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn set_api_key(profile: String, api_key: String) -> Result<(), String> {
+    profile_entry(&profile)?
+        .set_password(&api_key)
+        .map_err(|e| e.to_string())?;
+
+    let mut profiles = load_profile_index()?;
+    if !profiles.contains(&profile) {
+        profiles.push(profile);
+        save_profile_index(&profiles)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_api_key(profile: String) -> Result<Option<String>, String> {
+    get_stored_key(&profile)
+}
+
+#[tauri::command]
+pub fn delete_api_key(profile: String) -> Result<(), String> {
+    match profile_entry(&profile)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => {}
+        Err(e) => return Err(e.to_string()),
+    }
+
+    let mut profiles = load_profile_index()?;
+    profiles.retain(|p| p != &profile);
+    save_profile_index(&profiles)
+}
+
+#[tauri::command]
+pub fn list_api_keys() -> Result<Vec<String>, String> {
+    load_profile_index()
+}
@@ -0,0 +1,86 @@
+//! Headless CLI sharing the keyring profiles and Rally request path with
+//! the Tauri app, so scripts and CI can query Rally with the credential
+//! the GUI already stored.
+
+use clap::{Parser, Subcommand};
+use rally_notifier::{accounts, perform_rally_request, RallyRequest, DEFAULT_PROFILE};
+
+const RALLY_API_BASE: &str = "https://rally1.rallydev.com/slm/webservice/v2.0";
+
+#[derive(Parser)]
+#[command(name = "rally_cli", about = "Query Rally using a stored rally-notifier credential")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Keyring profile to read/write (see the app's account switcher).
+    #[arg(long, global = true, default_value = DEFAULT_PROFILE)]
+    profile: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// GET an arbitrary Rally WSAPI URL.
+    Get { url: String },
+    /// Run a WSAPI query against an artifact type, e.g. `query defect --query "(State = Open)"`.
+    Query {
+        #[arg(value_name = "TYPE")]
+        artifact_type: String,
+        #[arg(long)]
+        query: String,
+    },
+    /// Store an API key under --profile.
+    SetKey { api_key: String },
+    /// Remove the API key stored under --profile.
+    DeleteKey,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Get { url } => run_request(&cli.profile, url).await,
+        Command::Query { artifact_type, query } => {
+            let mut url = match reqwest::Url::parse(&format!("{RALLY_API_BASE}/{artifact_type}")) {
+                Ok(url) => url,
+                Err(e) => {
+                    eprintln!("error: invalid artifact type '{artifact_type}': {e}");
+                    std::process::exit(1);
+                }
+            };
+            url.query_pairs_mut().append_pair("query", &query);
+            run_request(&cli.profile, url.to_string()).await
+        }
+        Command::SetKey { api_key } => accounts::set_api_key(cli.profile.clone(), api_key),
+        Command::DeleteKey => accounts::delete_api_key(cli.profile.clone()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run_request(profile: &str, url: String) -> Result<(), String> {
+    let request = RallyRequest {
+        url,
+        method: "GET".to_string(),
+        body: None,
+        api_key: None,
+        profile: Some(profile.to_string()),
+    };
+
+    let response = perform_rally_request(request).await?;
+    println!("{}", response.body);
+
+    if response.status >= 400 || !response.errors.is_empty() {
+        return Err(format!(
+            "rally request failed (http {}, {} error(s))",
+            response.status,
+            response.errors.len()
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+const HOTKEY_FILE: &str = "hotkey.json";
+const DEFAULT_KEY_COMBO: &str = "CommandOrControl+Shift+R";
+
+/// The show/hide global shortcut, persisted so it survives a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyConfig {
+    pub key_combo: String,
+    pub enabled: bool,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            key_combo: DEFAULT_KEY_COMBO.to_string(),
+            enabled: true,
+        }
+    }
+}
+
+fn config_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(HOTKEY_FILE))
+}
+
+fn load_config(app: &AppHandle) -> HotkeyConfig {
+    let Ok(path) = config_path(app) else {
+        return HotkeyConfig::default();
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_config(app: &AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+    let path = config_path(app)?;
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Swaps the active global shortcut for `config.key_combo`, unregistering
+/// whatever was bound before. Binding conflicts are reported back to the
+/// caller instead of panicking.
+fn apply(app: &AppHandle, config: &HotkeyConfig) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    let _ = shortcuts.unregister_all();
+
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let shortcut = Shortcut::from_str(&config.key_combo)
+        .map_err(|e| format!("invalid key combo '{}': {e}", config.key_combo))?;
+
+    shortcuts.register(shortcut).map_err(|e| {
+        format!(
+            "could not bind '{}', it may already be in use by another app: {e}",
+            config.key_combo
+        )
+    })
+}
+
+/// Loads the persisted hotkey and registers it. Called once from `setup`;
+/// a failed binding is logged rather than treated as fatal, since the rest
+/// of the app works fine without the shortcut.
+pub fn init(app: &AppHandle) {
+    let config = load_config(app);
+    if let Err(e) = apply(app, &config) {
+        eprintln!("global hotkey not active: {e}");
+    }
+}
+
+#[tauri::command]
+pub fn set_hotkey(app: AppHandle, key_combo: String, enabled: bool) -> Result<(), String> {
+    let config = HotkeyConfig { key_combo, enabled };
+    apply(&app, &config)?;
+    save_config(&app, &config)
+}
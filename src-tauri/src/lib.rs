@@ -0,0 +1,96 @@
+//! Shared core linked by both the Tauri app (`main.rs`) and the headless
+//! `rally_cli` binary: the keyring-backed profiles and the retrying Rally
+//! WSAPI request path.
+
+pub mod accounts;
+pub mod retry;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+pub const SERVICE_NAME: &str = "rally-notifier";
+pub const DEFAULT_PROFILE: &str = "rally-api-key";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RallyRequest {
+    pub url: String,
+    pub method: String,
+    pub body: Option<String>,
+    #[serde(rename = "apiKey")]
+    pub api_key: Option<String>,
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RallyResponse {
+    pub status: u16,
+    pub body: String,
+    pub errors: Vec<String>,
+    pub retries: u32,
+}
+
+/// Resolves the `ZSESSIONID` to send: a named profile's stored key takes
+/// precedence, falling back to a caller-supplied key for callers that
+/// haven't adopted profiles.
+fn resolve_api_key(request: &RallyRequest) -> Result<String, String> {
+    if let Some(profile) = &request.profile {
+        return accounts::get_stored_key(profile)?
+            .ok_or_else(|| format!("no stored API key for profile '{profile}'"));
+    }
+    request
+        .api_key
+        .clone()
+        .ok_or_else(|| "request is missing both 'apiKey' and 'profile'".to_string())
+}
+
+/// The one place a Rally WSAPI request is actually sent — used by the
+/// Tauri `rally_request` command, the polling subsystem, the local proxy,
+/// and `rally_cli`. Retries connection errors and Rally's 429/503
+/// throttling responses with exponential backoff before giving up.
+pub async fn perform_rally_request(request: RallyRequest) -> Result<RallyResponse, String> {
+    let method = Method::from_bytes(request.method.as_bytes()).map_err(|e| e.to_string())?;
+    let api_key = resolve_api_key(&request)?;
+    let client = reqwest::Client::new();
+
+    let mut attempt = 0;
+    loop {
+        let mut builder = client
+            .request(method.clone(), request.url.clone())
+            .header("Content-Type", "application/json")
+            .header("ZSESSIONID", api_key.clone());
+
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+
+        let response = match builder.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                if attempt >= retry::MAX_RETRIES {
+                    return Err(e.to_string());
+                }
+                tokio::time::sleep(retry::delay_for_attempt(attempt, None)).await;
+                attempt += 1;
+                continue;
+            }
+        };
+
+        let status = response.status().as_u16();
+        if retry::is_retryable_status(status) && attempt < retry::MAX_RETRIES {
+            let retry_after = retry::retry_after_duration(&response);
+            tokio::time::sleep(retry::delay_for_attempt(attempt, retry_after)).await;
+            attempt += 1;
+            continue;
+        }
+
+        let body = response.text().await.map_err(|e| e.to_string())?;
+        let errors = retry::extract_rally_errors(&body);
+
+        return Ok(RallyResponse {
+            status,
+            body,
+            errors,
+            retries: attempt,
+        });
+    }
+}
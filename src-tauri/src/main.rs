@@ -1,84 +1,76 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use keyring::Entry;
-use reqwest::Method;
-use serde::{Deserialize, Serialize};
+mod hotkey;
+mod polling;
+mod server;
+mod tray;
 
-const SERVICE_NAME: &str = "rally-notifier";
-const ACCOUNT_NAME: &str = "rally-api-key";
-
-#[derive(Debug, Deserialize)]
-struct RallyRequest {
-    url: String,
-    method: String,
-    body: Option<String>,
-    #[serde(rename = "apiKey")]
-    api_key: String,
-}
-
-#[derive(Debug, Serialize)]
-struct RallyResponse {
-    status: u16,
-    body: String,
-}
-
-#[tauri::command]
-fn set_api_key(api_key: String) -> Result<(), String> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME).map_err(|e| e.to_string())?;
-    entry.set_password(&api_key).map_err(|e| e.to_string())
-}
-
-#[tauri::command]
-fn get_api_key() -> Result<Option<String>, String> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME).map_err(|e| e.to_string())?;
-    match entry.get_password() {
-        Ok(value) => Ok(Some(value)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(e) => Err(e.to_string())
-    }
-}
-
-#[tauri::command]
-fn delete_api_key() -> Result<(), String> {
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME).map_err(|e| e.to_string())?;
-    match entry.delete_password() {
-        Ok(_) => Ok(()),
-        Err(keyring::Error::NoEntry) => Ok(()),
-        Err(e) => Err(e.to_string())
-    }
-}
+use rally_notifier::{accounts, perform_rally_request, RallyRequest, RallyResponse};
+use tauri::Manager;
 
 #[tauri::command]
 async fn rally_request(request: RallyRequest) -> Result<RallyResponse, String> {
-    let method = Method::from_bytes(request.method.as_bytes()).map_err(|e| e.to_string())?;
-    let client = reqwest::Client::new();
-
-    let mut builder = client
-        .request(method, request.url)
-        .header("Content-Type", "application/json")
-        .header("ZSESSIONID", request.api_key);
-
-    if let Some(body) = request.body {
-        builder = builder.body(body);
-    }
-
-    let response = builder.send().await.map_err(|e| e.to_string())?;
-    let status = response.status().as_u16();
-    let body = response.text().await.map_err(|e| e.to_string())?;
-
-    Ok(RallyResponse { status, body })
+    perform_rally_request(request).await
 }
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        tray::toggle_main_window(app);
+                    }
+                })
+                .build(),
+        )
+        .manage(polling::PollingState::default())
+        .manage(server::ProxyState::default())
+        .setup(|app| {
+            polling::spawn_poll_loop(app.handle().clone());
+            server::spawn_proxy(app.handle().clone());
+            tray::build(app.handle())?;
+            hotkey::init(app.handle());
+
+            if let Some(window) = app.get_webview_window("main") {
+                let hide_target = window.clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                        api.prevent_close();
+                        let _ = hide_target.hide();
+                    }
+                });
+            }
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
-            set_api_key,
-            get_api_key,
-            delete_api_key,
-            rally_request
+            accounts::set_api_key,
+            accounts::get_api_key,
+            accounts::delete_api_key,
+            accounts::list_api_keys,
+            rally_request,
+            polling::register_query,
+            polling::start_polling,
+            polling::stop_polling,
+            polling::set_poll_interval,
+            server::approve_connection,
+            hotkey::set_hotkey
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app_handle, event| {
+            // Closing the window only hides it (see the `CloseRequested`
+            // handler above); this additionally keeps the process itself
+            // alive for requests that bypass the window, like Cmd+Q.
+            // `code` is only `Some` for an explicit `app.exit(...)` (e.g. the
+            // tray's Quit item), which must still be allowed to terminate.
+            if let tauri::RunEvent::ExitRequested { code, api, .. } = event {
+                if code.is_none() {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Mutex;
+
+use rally_notifier::{perform_rally_request, RallyRequest};
+
+const QUERIES_FILE: &str = "poll_queries.json";
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+const MIN_INTERVAL_SECS: u64 = 5;
+
+/// A saved Rally query the polling loop should re-run on each tick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollQuery {
+    pub id: String,
+    pub name: String,
+    pub request: RallyRequest,
+}
+
+/// What we last saw for a single query, so the next poll can diff against it.
+#[derive(Debug, Default)]
+struct ArtifactSnapshot {
+    // ObjectID -> LastUpdateDate
+    seen: HashMap<String, String>,
+    initialized: bool,
+}
+
+pub struct PollingState {
+    enabled: AtomicBool,
+    interval_secs: AtomicU64,
+    next_id: AtomicU64,
+    queries: Mutex<Vec<PollQuery>>,
+    snapshots: Mutex<HashMap<String, ArtifactSnapshot>>,
+}
+
+impl Default for PollingState {
+    fn default() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            interval_secs: AtomicU64::new(DEFAULT_INTERVAL_SECS),
+            next_id: AtomicU64::new(1),
+            queries: Mutex::new(Vec::new()),
+            snapshots: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+fn queries_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(QUERIES_FILE))
+}
+
+fn load_persisted_queries(app: &AppHandle) -> Vec<PollQuery> {
+    let Ok(path) = queries_path(app) else {
+        return Vec::new();
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_persisted_queries(app: &AppHandle, queries: &[PollQuery]) -> Result<(), String> {
+    let path = queries_path(app)?;
+    let contents = serde_json::to_string_pretty(queries).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// The next `query-N` id to mint, one past the highest numeric suffix
+/// already in `queries` — otherwise a fresh `next_id` counter on restart
+/// reissues an id a persisted query already owns, and the two end up
+/// sharing one `ArtifactSnapshot`.
+fn next_id_seed(queries: &[PollQuery]) -> u64 {
+    queries
+        .iter()
+        .filter_map(|query| query.id.strip_prefix("query-"))
+        .filter_map(|suffix| suffix.parse::<u64>().ok())
+        .max()
+        .map_or(1, |highest| highest + 1)
+}
+
+#[tauri::command]
+pub async fn register_query(
+    app: AppHandle,
+    state: State<'_, PollingState>,
+    name: String,
+    request: RallyRequest,
+) -> Result<String, String> {
+    let id = format!("query-{}", state.next_id.fetch_add(1, Ordering::SeqCst));
+
+    let mut queries = state.queries.lock().await;
+    queries.push(PollQuery {
+        id: id.clone(),
+        name,
+        request,
+    });
+    save_persisted_queries(&app, &queries)?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn start_polling(state: State<'_, PollingState>) {
+    state.enabled.store(true, Ordering::SeqCst);
+}
+
+#[tauri::command]
+pub fn stop_polling(state: State<'_, PollingState>) {
+    state.enabled.store(false, Ordering::SeqCst);
+}
+
+#[tauri::command]
+pub fn set_poll_interval(state: State<'_, PollingState>, seconds: u64) {
+    state
+        .interval_secs
+        .store(seconds.max(MIN_INTERVAL_SECS), Ordering::SeqCst);
+}
+
+/// Runs every registered query once, ignoring the enabled flag — backs the
+/// tray's "Poll Now" menu item.
+pub fn poll_now(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let queries = app.state::<PollingState>().queries.lock().await.clone();
+        for query in &queries {
+            if let Err(e) = poll_once(&app, query).await {
+                eprintln!("poll of '{}' failed: {e}", query.name);
+            }
+        }
+    });
+}
+
+/// Spawned once from the Tauri `setup` hook. Runs for the lifetime of the
+/// app; `start_polling`/`stop_polling` just flip whether a tick does
+/// anything, so there is no separate task to hand out and cancel.
+pub fn spawn_poll_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        {
+            let state = app.state::<PollingState>();
+            let persisted = load_persisted_queries(&app);
+            state.next_id.store(next_id_seed(&persisted), Ordering::SeqCst);
+            *state.queries.lock().await = persisted;
+        }
+
+        loop {
+            let interval = app.state::<PollingState>().interval_secs.load(Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            if !app.state::<PollingState>().enabled.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            let queries = app.state::<PollingState>().queries.lock().await.clone();
+            for query in &queries {
+                if let Err(e) = poll_once(&app, query).await {
+                    eprintln!("poll of '{}' failed, will retry next tick: {e}", query.name);
+                }
+            }
+        }
+    });
+}
+
+async fn poll_once(app: &AppHandle, query: &PollQuery) -> Result<(), String> {
+    let response = perform_rally_request(query.request.clone()).await?;
+    if response.status >= 400 {
+        return Err(format!("http {}", response.status));
+    }
+
+    let parsed: Value = serde_json::from_str(&response.body).map_err(|e| e.to_string())?;
+    let results = parsed
+        .get("QueryResult")
+        .and_then(|qr| qr.get("Results"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let state = app.state::<PollingState>();
+    let mut snapshots = state.snapshots.lock().await;
+    let snapshot = snapshots.entry(query.id.clone()).or_default();
+    let changed_items = apply_results_to_snapshot(snapshot, &results);
+
+    for item in &changed_items {
+        notify_change(app, item);
+    }
+
+    Ok(())
+}
+
+/// Diffs `results` against `snapshot`, updates it in place, and returns the
+/// items that are new or changed since the last poll. Pulled out of
+/// `poll_once` so the diffing itself can be tested without a live Rally
+/// connection.
+fn apply_results_to_snapshot(snapshot: &mut ArtifactSnapshot, results: &[Value]) -> Vec<Value> {
+    let is_baseline_poll = !snapshot.initialized;
+    let mut changed_items = Vec::new();
+
+    for item in results {
+        let Some(object_id) = object_id_string(item) else {
+            continue;
+        };
+        let last_update = item
+            .get("LastUpdateDate")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        let changed = match snapshot.seen.get(&object_id) {
+            Some(previous) => previous != &last_update,
+            // Never seen before: only a genuinely new item once we have a
+            // baseline to compare against, not on the first poll ever.
+            None => !is_baseline_poll,
+        };
+        snapshot.seen.insert(object_id, last_update);
+
+        if changed {
+            changed_items.push(item.clone());
+        }
+    }
+
+    snapshot.initialized = true;
+    changed_items
+}
+
+fn object_id_string(item: &Value) -> Option<String> {
+    match item.get("ObjectID")? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn notify_change(app: &AppHandle, item: &Value) {
+    let formatted_id = item
+        .get("FormattedID")
+        .and_then(Value::as_str)
+        .unwrap_or("Rally item");
+    let summary = item
+        .get("Name")
+        .or_else(|| item.get("_refObjectName"))
+        .and_then(Value::as_str)
+        .unwrap_or("was updated");
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(formatted_id)
+        .body(summary)
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query_with_id(id: &str) -> PollQuery {
+        PollQuery {
+            id: id.to_string(),
+            name: id.to_string(),
+            request: RallyRequest {
+                url: "https://rally1.rallydev.com".to_string(),
+                method: "GET".to_string(),
+                body: None,
+                api_key: None,
+                profile: None,
+            },
+        }
+    }
+
+    #[test]
+    fn next_id_seed_continues_past_the_highest_persisted_suffix() {
+        let queries = vec![query_with_id("query-1"), query_with_id("query-3"), query_with_id("query-2")];
+        assert_eq!(next_id_seed(&queries), 4);
+    }
+
+    #[test]
+    fn next_id_seed_starts_at_one_with_no_persisted_queries() {
+        assert_eq!(next_id_seed(&[]), 1);
+    }
+
+    #[test]
+    fn next_id_seed_ignores_ids_that_dont_match_the_query_n_shape() {
+        let queries = vec![query_with_id("query-5"), query_with_id("legacy-id")];
+        assert_eq!(next_id_seed(&queries), 6);
+    }
+
+    #[test]
+    fn object_id_string_handles_string_and_number_forms() {
+        assert_eq!(
+            object_id_string(&json!({"ObjectID": "US123"})),
+            Some("US123".to_string())
+        );
+        assert_eq!(object_id_string(&json!({"ObjectID": 456})), Some("456".to_string()));
+        assert_eq!(object_id_string(&json!({"Name": "no id"})), None);
+    }
+
+    #[test]
+    fn baseline_poll_records_items_without_reporting_changes() {
+        let mut snapshot = ArtifactSnapshot::default();
+        let results = vec![json!({"ObjectID": 1, "LastUpdateDate": "2026-01-01T00:00:00Z"})];
+
+        let changed = apply_results_to_snapshot(&mut snapshot, &results);
+
+        assert!(changed.is_empty());
+        assert!(snapshot.initialized);
+        assert_eq!(snapshot.seen.get("1"), Some(&"2026-01-01T00:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn later_poll_reports_updated_and_newly_seen_items() {
+        let mut snapshot = ArtifactSnapshot::default();
+        let baseline = vec![json!({"ObjectID": 1, "LastUpdateDate": "2026-01-01T00:00:00Z"})];
+        apply_results_to_snapshot(&mut snapshot, &baseline);
+
+        let next = vec![
+            json!({"ObjectID": 1, "LastUpdateDate": "2026-01-02T00:00:00Z"}), // changed
+            json!({"ObjectID": 2, "LastUpdateDate": "2026-01-01T00:00:00Z"}), // brand new
+        ];
+        let changed = apply_results_to_snapshot(&mut snapshot, &next);
+
+        assert_eq!(changed.len(), 2);
+    }
+
+    #[test]
+    fn unchanged_item_is_not_reported_again() {
+        let mut snapshot = ArtifactSnapshot::default();
+        let results = vec![json!({"ObjectID": 1, "LastUpdateDate": "2026-01-01T00:00:00Z"})];
+        apply_results_to_snapshot(&mut snapshot, &results);
+
+        let changed = apply_results_to_snapshot(&mut snapshot, &results);
+
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn items_without_an_object_id_are_skipped() {
+        let mut snapshot = ArtifactSnapshot::default();
+        let results = vec![json!({"LastUpdateDate": "2026-01-01T00:00:00Z"})];
+
+        let changed = apply_results_to_snapshot(&mut snapshot, &results);
+
+        assert!(changed.is_empty());
+        assert!(snapshot.seen.is_empty());
+    }
+}
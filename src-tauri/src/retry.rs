@@ -0,0 +1,139 @@
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use serde_json::Value;
+
+pub(crate) const MAX_RETRIES: u32 = 5;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// `Retry-After` wins when Rally sends one; otherwise exponential backoff
+/// with equal jitter (half the capped delay, plus up to the other half),
+/// so retries don't all land on the same tick.
+pub(crate) fn delay_for_attempt(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after.unwrap_or_else(|| backoff_with_jitter(attempt))
+}
+
+fn backoff_with_jitter(attempt: u32) -> Duration {
+    let exponential = BASE_BACKOFF * 2u32.pow(attempt.min(6));
+    let capped = exponential.min(MAX_BACKOFF);
+    let half = capped / 2;
+    half + Duration::from_millis(jitter_ms(half.as_millis() as u64))
+}
+
+fn jitter_ms(max_ms: u64) -> u64 {
+    if max_ms == 0 {
+        return 0;
+    }
+    rand::thread_rng().gen_range(0..=max_ms)
+}
+
+pub(crate) fn retry_after_duration(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP-date (RFC 7231
+/// ยง7.1.3), so try both forms before giving up on the header.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value).ok()?;
+    target.duration_since(SystemTime::now()).ok()
+}
+
+pub(crate) fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status == 503
+}
+
+/// Rally's WSAPI wraps results under a single top-level key (e.g.
+/// `QueryResult`, `OperationResult`, `CreateResult`) that carries an
+/// `Errors` array even on an HTTP 200 — pull those out so a 200 with
+/// embedded errors doesn't read as success.
+pub(crate) fn extract_rally_errors(body: &str) -> Vec<String> {
+    let Ok(parsed) = serde_json::from_str::<Value>(body) else {
+        return Vec::new();
+    };
+    let Some(obj) = parsed.as_object() else {
+        return Vec::new();
+    };
+
+    for value in obj.values() {
+        if let Some(errors) = value.get("Errors").and_then(Value::as_array) {
+            let messages: Vec<String> = errors
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+            if !messages.is_empty() {
+                return messages;
+            }
+        }
+    }
+
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_with_jitter_never_exceeds_the_cap() {
+        for attempt in 0..10 {
+            assert!(backoff_with_jitter(attempt) <= MAX_BACKOFF);
+        }
+    }
+
+    #[test]
+    fn backoff_with_jitter_grows_with_attempt_before_capping() {
+        // Attempt 0's window (half..=full of the base backoff) sits well
+        // below a much later attempt's, which has already hit the cap.
+        assert!(backoff_with_jitter(0) < BASE_BACKOFF * 2);
+        assert!(backoff_with_jitter(10) >= MAX_BACKOFF / 2);
+        assert!(backoff_with_jitter(10) <= MAX_BACKOFF);
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_seconds() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_http_dates_in_the_future() {
+        let delay = parse_retry_after("Thu, 01 Jan 2099 00:00:00 GMT");
+        assert!(delay.is_some());
+        assert!(delay.unwrap() > Duration::from_secs(0));
+    }
+
+    #[test]
+    fn parse_retry_after_rejects_garbage() {
+        assert_eq!(parse_retry_after("not-a-retry-after-value"), None);
+    }
+
+    #[test]
+    fn extract_rally_errors_finds_errors_nested_under_the_result_key() {
+        let body = r#"{"OperationResult":{"Errors":["Field is required"],"Warnings":[]}}"#;
+        assert_eq!(extract_rally_errors(body), vec!["Field is required".to_string()]);
+    }
+
+    #[test]
+    fn extract_rally_errors_is_empty_when_no_errors_are_present() {
+        let body = r#"{"QueryResult":{"Results":[]}}"#;
+        assert!(extract_rally_errors(body).is_empty());
+    }
+
+    #[test]
+    fn extract_rally_errors_ignores_non_json_bodies() {
+        assert!(extract_rally_errors("not json").is_empty());
+    }
+}
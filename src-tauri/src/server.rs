@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use rally_notifier::{perform_rally_request, RallyRequest, DEFAULT_PROFILE};
+
+const PROXY_HOST: &str = "127.0.0.1";
+const PROXY_PORT: u16 = 47813;
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request from a local process, shaped like `RallyRequest` minus the key
+/// material — the proxy resolves that itself once the caller is approved.
+#[derive(Debug, Deserialize)]
+struct ProxyRequest {
+    url: String,
+    method: String,
+    body: Option<String>,
+    profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ConnectionRequestPayload {
+    request_id: String,
+    pid: u32,
+    exe_path: String,
+}
+
+#[derive(Default)]
+pub struct ProxyState {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<String, oneshot::Sender<bool>>>,
+    approved_exes: Mutex<HashSet<String>>,
+}
+
+/// Called by the UI in response to a `proxy-connection-request` event to
+/// let a specific pending connection through (or not).
+#[tauri::command]
+pub fn approve_connection(state: tauri::State<'_, ProxyState>, request_id: String, approve: bool) {
+    if let Some(sender) = state.pending.lock().unwrap().remove(&request_id) {
+        let _ = sender.send(approve);
+    }
+}
+
+/// Binds the local credential-broker proxy. Spawned once from `setup` and
+/// runs for the app's lifetime.
+pub fn spawn_proxy(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind((PROXY_HOST, PROXY_PORT)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("failed to bind local proxy on {PROXY_HOST}:{PROXY_PORT}: {e}");
+                return;
+            }
+        };
+
+        loop {
+            let (socket, peer_addr) = match listener.accept().await {
+                Ok(pair) => pair,
+                Err(e) => {
+                    eprintln!("proxy accept failed, continuing: {e}");
+                    continue;
+                }
+            };
+
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle_connection(&app, socket, peer_addr).await {
+                    eprintln!("proxy connection from {peer_addr} failed: {e}");
+                }
+            });
+        }
+    });
+}
+
+async fn handle_connection(
+    app: &AppHandle,
+    mut socket: TcpStream,
+    peer_addr: SocketAddr,
+) -> Result<(), String> {
+    let body = read_http_request_body(&mut socket).await?;
+    let request: ProxyRequest = serde_json::from_slice(&body).map_err(|e| e.to_string())?;
+
+    let Some((pid, exe_path)) = resolve_peer_process(peer_addr.port()) else {
+        return write_http_response(
+            &mut socket,
+            403,
+            r#"{"error":"could not identify the calling process"}"#,
+        )
+        .await;
+    };
+
+    if !is_approved_for_session(app, &exe_path) {
+        if request_approval(app, pid, &exe_path).await {
+            mark_approved(app, &exe_path);
+        } else {
+            return write_http_response(
+                &mut socket,
+                403,
+                r#"{"error":"connection denied"}"#,
+            )
+            .await;
+        }
+    }
+
+    let rally_request = RallyRequest {
+        url: request.url,
+        method: request.method,
+        body: request.body,
+        api_key: None,
+        // A caller that doesn't name a profile gets whatever the GUI has
+        // stored under the default one, rather than a hard failure.
+        profile: Some(request.profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string())),
+    };
+
+    match perform_rally_request(rally_request).await {
+        Ok(response) => {
+            let body = serde_json::to_string(&response).map_err(|e| e.to_string())?;
+            write_http_response(&mut socket, 200, &body).await
+        }
+        Err(e) => {
+            let body = serde_json::json!({ "error": e }).to_string();
+            write_http_response(&mut socket, 502, &body).await
+        }
+    }
+}
+
+async fn request_approval(app: &AppHandle, pid: u32, exe_path: &str) -> bool {
+    let state = app.state::<ProxyState>();
+    let request_id = format!("conn-{}", state.next_id.fetch_add(1, Ordering::SeqCst));
+
+    let (tx, rx) = oneshot::channel();
+    state.pending.lock().unwrap().insert(request_id.clone(), tx);
+
+    let emitted = app.emit(
+        "proxy-connection-request",
+        ConnectionRequestPayload {
+            request_id: request_id.clone(),
+            pid,
+            exe_path: exe_path.to_string(),
+        },
+    );
+    if emitted.is_err() {
+        state.pending.lock().unwrap().remove(&request_id);
+        return false;
+    }
+
+    // Deny by default: no response within the timeout (no UI open, or the
+    // user ignores the prompt) must not leave the broker hanging forever.
+    match tokio::time::timeout(APPROVAL_TIMEOUT, rx).await {
+        Ok(Ok(approved)) => approved,
+        _ => {
+            state.pending.lock().unwrap().remove(&request_id);
+            false
+        }
+    }
+}
+
+fn is_approved_for_session(app: &AppHandle, exe_path: &str) -> bool {
+    app.state::<ProxyState>()
+        .approved_exes
+        .lock()
+        .unwrap()
+        .contains(exe_path)
+}
+
+fn mark_approved(app: &AppHandle, exe_path: &str) {
+    app.state::<ProxyState>()
+        .approved_exes
+        .lock()
+        .unwrap()
+        .insert(exe_path.to_string());
+}
+
+/// Resolves the process on the other end of a loopback connection by
+/// matching its local port against the TCP table, then looking up the
+/// owning PID's executable path.
+fn resolve_peer_process(peer_port: u16) -> Option<(u32, String)> {
+    use netstat2::{iterate_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo};
+
+    let sockets = iterate_sockets_info(AddressFamilyFlags::IPV4, ProtocolFlags::TCP).ok()?;
+
+    for info in sockets.flatten() {
+        let ProtocolSocketInfo::Tcp(tcp) = &info.protocol_socket_info else {
+            continue;
+        };
+        if tcp.local_port != peer_port {
+            continue;
+        }
+        let pid = *info.associated_pids.first()?;
+
+        let mut system = sysinfo::System::new();
+        system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        let process = system.process(sysinfo::Pid::from_u32(pid))?;
+        let exe_path = process
+            .exe()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| process.name().to_string_lossy().to_string());
+
+        return Some((pid, exe_path));
+    }
+
+    None
+}
+
+/// Reads just enough of a raw HTTP/1.1 request to get at the JSON body;
+/// the proxy only ever expects a single `POST` with a `Content-Length`.
+/// Generic over `AsyncRead` so tests can drive it with an in-memory pipe
+/// instead of a real `TcpStream`.
+async fn read_http_request_body<S: tokio::io::AsyncRead + Unpin>(
+    stream: &mut S,
+) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("connection closed before headers were complete".to_string());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_subslice(&buf, b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let headers = String::from_utf8_lossy(&buf[..header_end]);
+    let content_length = parse_content_length(&headers);
+
+    let mut body = buf[header_end..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            // Connection closed early: return whatever arrived rather than
+            // erroring, since the caller still gets a usable (if short) body.
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(body)
+}
+
+fn parse_content_length(headers: &str) -> usize {
+    headers
+        .lines()
+        .find_map(|line| {
+            line.to_ascii_lowercase().starts_with("content-length:").then(|| {
+                line.split(':').nth(1).unwrap_or("0").trim().parse().unwrap_or(0)
+            })
+        })
+        .unwrap_or(0)
+}
+
+async fn write_http_response(stream: &mut TcpStream, status: u16, body: &str) -> Result<(), String> {
+    let reason = match status {
+        200 => "OK",
+        403 => "Forbidden",
+        502 => "Bad Gateway",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_subslice_locates_the_header_terminator() {
+        assert_eq!(find_subslice(b"abc\r\n\r\ndef", b"\r\n\r\n"), Some(3));
+        assert_eq!(find_subslice(b"no terminator here", b"\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn parse_content_length_is_case_insensitive_and_defaults_to_zero() {
+        assert_eq!(parse_content_length("POST / HTTP/1.1\r\ncontent-length: 42\r\n"), 42);
+        assert_eq!(parse_content_length("POST / HTTP/1.1\r\nContent-Length: 7\r\n"), 7);
+        assert_eq!(parse_content_length("POST / HTTP/1.1\r\n"), 0);
+    }
+
+    #[tokio::test]
+    async fn read_http_request_body_reassembles_a_body_split_across_reads() {
+        let (mut client, mut server) = tokio::io::duplex(8);
+        let request = b"POST / HTTP/1.1\r\nContent-Length: 11\r\n\r\nhello world";
+
+        let writer = tokio::spawn(async move {
+            for chunk in request.chunks(4) {
+                client.write_all(chunk).await.unwrap();
+                client.flush().await.unwrap();
+            }
+        });
+
+        let body = read_http_request_body(&mut server).await.unwrap();
+        writer.await.unwrap();
+
+        assert_eq!(body, b"hello world");
+    }
+
+    #[tokio::test]
+    async fn read_http_request_body_returns_whatever_arrived_if_the_connection_closes_early() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        let request = b"POST / HTTP/1.1\r\nContent-Length: 20\r\n\r\nshort";
+
+        client.write_all(request).await.unwrap();
+        drop(client);
+
+        let body = read_http_request_body(&mut server).await.unwrap();
+
+        assert_eq!(body, b"short");
+    }
+}
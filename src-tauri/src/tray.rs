@@ -0,0 +1,55 @@
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager};
+
+const MAIN_WINDOW: &str = "main";
+
+/// Builds the tray icon and its "Show" / "Poll Now" / "Quit" menu. Called
+/// once from `setup`.
+pub fn build(app: &AppHandle) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
+    let poll_now = MenuItem::with_id(app, "poll_now", "Poll Now", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show, &poll_now, &quit])?;
+
+    let mut tray = TrayIconBuilder::new().menu(&menu).on_menu_event(|app, event| {
+        match event.id.as_ref() {
+            "show" => show_main_window(app),
+            "poll_now" => crate::polling::poll_now(app.clone()),
+            "quit" => app.exit(0),
+            _ => {}
+        }
+    });
+
+    // No `tauri.conf.json` in this tree guarantees a bundled default icon;
+    // fall back to the platform's tray default rather than failing setup.
+    match app.default_window_icon() {
+        Some(icon) => tray = tray.icon(icon.clone()),
+        None => eprintln!("no default window icon bundled; tray will use the platform default"),
+    }
+
+    tray.build(app)?;
+
+    Ok(())
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Used by the global shortcut handler to flip the main window's visibility.
+pub fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}